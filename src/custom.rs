@@ -0,0 +1,100 @@
+//! Runtime registry for custom modalities.
+//!
+//! [`Modality`] reserves only the five built-in bits (`AUDIO`, `IMAGE`,
+//! `TEXT`, `VIDEO`, `OTHER`), leaving the remaining 27 bits of the
+//! underlying `u32` free. This module lets applications claim one of
+//! those bits for a domain-specific modality (e.g. "pointcloud", "imu")
+//! via [`crate::Modality::register`], so it can flow through
+//! [`crate::Modality::to_names`] and [`crate::Modality::from_names`]
+//! alongside the built-ins.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::Modality;
+
+const FIRST_CUSTOM_BIT: u32 = 1 << 5;
+
+const RESERVED_NAMES: &[&str] = &["audio", "image", "text", "video", "other", "none", "all"];
+
+struct Registry {
+    name_to_bit: HashMap<&'static str, u32>,
+    bit_to_name: HashMap<u32, &'static str>,
+    next_bit: u32,
+}
+
+fn registry() -> &'static Mutex<Registry> {
+    static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        Mutex::new(Registry {
+            name_to_bit: HashMap::new(),
+            bit_to_name: HashMap::new(),
+            next_bit: FIRST_CUSTOM_BIT,
+        })
+    })
+}
+
+pub(crate) fn register(name: &str) -> Result<Modality, String> {
+    if RESERVED_NAMES.contains(&name) {
+        return Err(format!("Modality name already in use: {}", name));
+    }
+
+    let mut registry = registry().lock().unwrap();
+    if registry.name_to_bit.contains_key(name) {
+        return Err(format!("Modality name already in use: {}", name));
+    }
+    if registry.next_bit == 0 {
+        return Err("No free bits remaining to register a custom modality".to_string());
+    }
+
+    let bit = registry.next_bit;
+    registry.next_bit <<= 1;
+
+    let leaked: &'static str = Box::leak(name.to_string().into_boxed_str());
+    registry.name_to_bit.insert(leaked, bit);
+    registry.bit_to_name.insert(bit, leaked);
+
+    Ok(Modality::from_bits_retain(bit))
+}
+
+pub(crate) fn name_for_bit(bit: u32) -> Option<&'static str> {
+    registry().lock().unwrap().bit_to_name.get(&bit).copied()
+}
+
+pub(crate) fn bit_for_name(name: &str) -> Option<u32> {
+    registry().lock().unwrap().name_to_bit.get(name).copied()
+}
+
+#[cfg(test)]
+mod custom_tests {
+    use super::*;
+
+    // Each test registers uniquely-named modalities so the shared global
+    // registry doesn't leak state across tests.
+
+    #[test]
+    fn test_register_allocates_free_bit() {
+        let pointcloud = register("custom_test_pointcloud").unwrap();
+        assert!(pointcloud.bits() >= FIRST_CUSTOM_BIT);
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_name() {
+        register("custom_test_depth").unwrap();
+        let err = register("custom_test_depth").unwrap_err();
+        assert!(err.contains("already in use"));
+    }
+
+    #[test]
+    fn test_register_rejects_builtin_name() {
+        let err = register("audio").unwrap_err();
+        assert!(err.contains("already in use"));
+    }
+
+    #[test]
+    fn test_round_trip_name_and_bit() {
+        let modality = register("custom_test_imu").unwrap();
+        assert_eq!(name_for_bit(modality.bits()), Some("custom_test_imu"));
+        assert_eq!(bit_for_name("custom_test_imu"), Some(modality.bits()));
+    }
+}
@@ -0,0 +1,101 @@
+//! A trait for types that carry one or more [`Modality`], analogous to
+//! how capability-style traits expose what a type can do rather than
+//! requiring callers to know its concrete shape.
+//!
+//! This lets generic pipelines filter or route samples by modality
+//! without knowing whether a sample is, say, an audio buffer, a
+//! transcript, or a struct holding both.
+
+use crate::Modality;
+
+/// A set of modalities carried by some value. Currently just
+/// [`Modality`] itself; kept as a distinct name in [`HasModalities`]'s
+/// signature so call sites read in domain terms rather than bitflags
+/// terms.
+pub type ModalitySet = Modality;
+
+/// Implemented by types that can report which modalities they carry.
+pub trait HasModalities {
+    /// Returns the set of modalities this value carries.
+    fn modalities(&self) -> ModalitySet;
+
+    /// Returns `true` if this value carries `modality`.
+    fn supports(&self, modality: Modality) -> bool {
+        self.modalities().contains(modality)
+    }
+
+    /// Returns `true` if this value carries more than one modality.
+    fn is_multimodal(&self) -> bool {
+        self.modalities().count() > 1
+    }
+}
+
+impl HasModalities for Modality {
+    fn modalities(&self) -> ModalitySet {
+        *self
+    }
+}
+
+/// Implements [`HasModalities`] for a struct by delegating to one of its
+/// fields that already holds a [`Modality`].
+///
+/// ```
+/// use modalities::{impl_has_modalities, HasModalities, Modality};
+///
+/// struct Sample {
+///     modality: Modality,
+/// }
+///
+/// impl_has_modalities!(Sample, modality);
+///
+/// let sample = Sample { modality: Modality::AUDIO | Modality::TEXT };
+/// assert!(sample.is_multimodal());
+/// assert!(sample.supports(Modality::AUDIO));
+/// ```
+#[macro_export]
+macro_rules! impl_has_modalities {
+    ($ty:ty, $field:ident) => {
+        impl $crate::HasModalities for $ty {
+            fn modalities(&self) -> $crate::ModalitySet {
+                self.$field
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod has_modalities_tests {
+    use super::*;
+
+    struct Sample {
+        modality: Modality,
+    }
+
+    impl_has_modalities!(Sample, modality);
+
+    #[test]
+    fn test_modality_itself_implements_has_modalities() {
+        let combo = Modality::AUDIO | Modality::TEXT;
+        assert_eq!(combo.modalities(), combo);
+        assert!(combo.is_multimodal());
+    }
+
+    #[test]
+    fn test_supports_and_is_multimodal() {
+        let sample = Sample {
+            modality: Modality::AUDIO | Modality::TEXT,
+        };
+        assert!(sample.supports(Modality::AUDIO));
+        assert!(sample.supports(Modality::TEXT));
+        assert!(!sample.supports(Modality::VIDEO));
+        assert!(sample.is_multimodal());
+    }
+
+    #[test]
+    fn test_is_multimodal_false_for_single_modality() {
+        let sample = Sample {
+            modality: Modality::IMAGE,
+        };
+        assert!(!sample.is_multimodal());
+    }
+}
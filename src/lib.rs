@@ -1,6 +1,12 @@
 
 use bitflags::bitflags;
 
+mod custom;
+mod has_modalities;
+pub mod mime;
+
+pub use has_modalities::{HasModalities, ModalitySet};
+
 bitflags! {
     /// Represents one or more modalities in a multimodal system.
     ///
@@ -49,6 +55,13 @@ impl Modality {
         if self.contains(Modality::OTHER) {
             names.push("other");
         }
+        for modality in self.iter_modalities() {
+            if modality.bits() > Modality::OTHER.bits() {
+                if let Some(name) = custom::name_for_bit(modality.bits()) {
+                    names.push(name);
+                }
+            }
+        }
         names
     }
 
@@ -61,14 +74,188 @@ impl Modality {
                 "text" => Modality::TEXT,
                 "video" => Modality::VIDEO,
                 "other" => Modality::OTHER,
-                _ => return Err(format!("Invalid modality name: {}", name)),
+                other => match custom::bit_for_name(other) {
+                    Some(bit) => Modality::from_bits_retain(bit),
+                    None => return Err(format!("Invalid modality name: {}", other)),
+                },
             };
         }
         Ok(bits)
     }
+
+    /// Registers a new named modality occupying the next free bit beyond
+    /// the five built-in ones (27 bits are available), so it participates
+    /// in [`Modality::to_names`] and [`Modality::from_names`] alongside
+    /// `AUDIO`/`IMAGE`/`TEXT`/`VIDEO`/`OTHER`.
+    ///
+    /// Errors if `name` collides with a built-in or already-registered
+    /// name, or if every free bit has already been allocated.
+    pub fn register(name: &str) -> Result<Modality, String> {
+        custom::register(name)
+    }
+
+    /// Returns an iterator over the individual single-bit modalities
+    /// contained in `self`, e.g.
+    /// `(Modality::AUDIO | Modality::TEXT).iter_modalities()` yields
+    /// `Modality::AUDIO` then `Modality::TEXT`.
+    ///
+    /// This is distinct from bitflags' own generated [`Modality::iter`]:
+    /// that one walks the *named* flags (`AUDIO`, `IMAGE`, ...), while
+    /// this one walks every individual set bit, including bits allocated
+    /// by [`Modality::register`].
+    pub fn iter_modalities(self) -> ModalityIter {
+        ModalityIter { bits: self.bits() }
+    }
+
+    /// Returns the number of distinct single-bit modalities set in `self`.
+    pub fn count(&self) -> usize {
+        self.bits().count_ones() as usize
+    }
+
+    /// Alias for [`Modality::count`].
+    ///
+    /// Takes `&self` (rather than `self` by value, like `to_names` and
+    /// `iter_modalities`) so clippy's `len_without_is_empty` lint recognizes it as
+    /// paired with bitflags' generated `is_empty(&self)`.
+    pub fn len(&self) -> usize {
+        self.count()
+    }
+}
+
+/// Iterator over the individual single-bit modalities contained in a
+/// [`Modality`], yielded in ascending bit order.
+///
+/// Created by [`Modality::iter_modalities`].
+#[derive(Debug, Clone)]
+pub struct ModalityIter {
+    bits: u32,
+}
+
+impl Iterator for ModalityIter {
+    type Item = Modality;
+
+    fn next(&mut self) -> Option<Modality> {
+        if self.bits == 0 {
+            return None;
+        }
+        let lowest = self.bits & self.bits.wrapping_neg();
+        self.bits &= !lowest;
+        Some(Modality::from_bits_retain(lowest))
+    }
+}
+
+impl std::str::FromStr for Modality {
+    type Err = String;
+
+    /// Parses a combined-modality expression such as `"audio | text"`,
+    /// `"audio+video"`, `"none"`, or `"all"`.
+    ///
+    /// Tokens are separated by `|` or `+`, whitespace around each token
+    /// is trimmed, and matching is case-insensitive. Each token is
+    /// looked up with [`Modality::from_names`]; an unrecognized or empty
+    /// token produces an "Invalid modality token at position N" error
+    /// naming the token and its zero-based position in the expression.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Err("Invalid modality expression: empty string".to_string());
+        }
+
+        let lowered = trimmed.to_lowercase();
+        if lowered == "none" {
+            return Ok(Modality::NONE);
+        }
+        if lowered == "all" {
+            return Ok(Modality::ALL);
+        }
+
+        let mut bits = Modality::NONE;
+        for (position, token) in lowered.split(['|', '+']).enumerate() {
+            let token = token.trim();
+            if token.is_empty() {
+                return Err(format!(
+                    "Invalid modality token at position {}: empty token",
+                    position
+                ));
+            }
+            bits |= Modality::from_names(&[token]).map_err(|_| {
+                format!(
+                    "Invalid modality token at position {}: '{}'",
+                    position, token
+                )
+            })?;
+        }
+        Ok(bits)
+    }
+}
+
+#[cfg(feature = "serde")]
+pub mod serde_modality {
+    //! `serde` support for [`Modality`].
+    //!
+    //! Two wire representations are accepted when deserializing:
+    //! - an array of lowercase names, e.g. `["audio", "text"]`, matching
+    //!   [`Modality::from_names`]
+    //! - a plain integer bitmask matching [`Modality::bits`]
+    //!
+    //! Serialization always emits the array-of-names form, since that is
+    //! the human-friendly representation for config files and manifests.
+
+    use super::Modality;
+    use serde::de::{Error as DeError, Unexpected};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    impl Serialize for Modality {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            self.to_names().serialize(serializer)
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum ModalityRepr {
+        Names(Vec<String>),
+        Bits(u32),
+    }
+
+    impl<'de> Deserialize<'de> for Modality {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            match ModalityRepr::deserialize(deserializer)? {
+                ModalityRepr::Names(names) => {
+                    let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                    Modality::from_names(&names).map_err(DeError::custom)
+                }
+                ModalityRepr::Bits(bits) => {
+                    let unknown_bits = bits & !Modality::ALL.bits();
+                    let unknown_bits_are_registered = Modality::from_bits_retain(unknown_bits)
+                        .iter_modalities()
+                        .all(|bit| crate::custom::name_for_bit(bit.bits()).is_some());
+                    if unknown_bits_are_registered {
+                        Ok(Modality::from_bits_retain(bits))
+                    } else {
+                        Err(DeError::invalid_value(
+                            Unexpected::Unsigned(bits as u64),
+                            &"a valid Modality bitmask",
+                        ))
+                    }
+                }
+            }
+        }
+    }
 }
 
 #[cfg(feature = "python")]
+// pyo3 0.22's `#[pymethods]` macro expands `PyResult<Self>`-returning
+// methods into code that trips `clippy::useless_conversion`; the lint
+// fires in that macro-expanded code, outside the span of any one
+// method or impl block, so the allow has to live on the module.
+#[allow(clippy::useless_conversion)]
 pub mod python_modality {
     use super::Modality;
     use pyo3::prelude::*;
@@ -132,7 +319,7 @@ pub mod python_modality {
         }
 
         fn names(&self) -> Vec<String> {
-            let m = Modality::from_bits_truncate(self.bits);
+            let m = Modality::from_bits_retain(self.bits);
             m.to_names().into_iter().map(|s| s.to_string()).collect()
         }
 
@@ -144,6 +331,26 @@ pub mod python_modality {
                 names
             }
         }
+
+        /// Parses a combined-modality expression, e.g. `"audio | text"`.
+        ///
+        /// See [`Modality`]'s `FromStr` impl for the accepted syntax.
+        #[staticmethod]
+        fn parse(expr: &str) -> PyResult<Self> {
+            expr.parse()
+                .map_err(pyo3::exceptions::PyValueError::new_err)
+        }
+    }
+
+    impl std::str::FromStr for PyModality {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let modality: Modality = s.parse()?;
+            Ok(Self {
+                bits: modality.bits(),
+            })
+        }
     }
 }
 
@@ -245,4 +452,148 @@ mod modality_tests {
         assert!(!format!("{:?}", m).is_empty());
         assert_eq!(names.len(), 2);
     }
+
+    #[test]
+    fn test_iter_modalities_yields_each_set_modality() {
+        let combo = Modality::AUDIO | Modality::TEXT;
+        let collected: Vec<Modality> = combo.iter_modalities().collect();
+        assert_eq!(collected, vec![Modality::AUDIO, Modality::TEXT]);
+    }
+
+    #[test]
+    fn test_iter_modalities_empty_for_none() {
+        assert_eq!(Modality::NONE.iter_modalities().count(), 0);
+    }
+
+    #[test]
+    fn test_iter_modalities_matches_bitflags_iter_for_named_flags() {
+        // bitflags' own generated `iter()`/`IntoIterator` walk the named
+        // flags, skipping `NONE` and stopping before `ALL` once the
+        // single-bit flags have covered every bit; for a value made up
+        // only of named flags the two should agree.
+        let combo = Modality::ALL;
+        let via_bitflags_iter: Vec<Modality> = combo.into_iter().collect();
+        let via_iter_modalities: Vec<Modality> = combo.iter_modalities().collect();
+        assert_eq!(via_bitflags_iter, via_iter_modalities);
+        assert_eq!(via_iter_modalities.len(), 5);
+    }
+
+    #[test]
+    fn test_count_and_len() {
+        let combo = Modality::AUDIO | Modality::IMAGE | Modality::VIDEO;
+        assert_eq!(combo.count(), 3);
+        assert_eq!(combo.len(), combo.count());
+    }
+
+    #[test]
+    fn test_from_str_pipe_and_plus_separators() {
+        assert_eq!(
+            "audio | text".parse::<Modality>().unwrap(),
+            Modality::AUDIO | Modality::TEXT
+        );
+        assert_eq!(
+            "audio+video".parse::<Modality>().unwrap(),
+            Modality::AUDIO | Modality::VIDEO
+        );
+    }
+
+    #[test]
+    fn test_from_str_special_tokens() {
+        assert_eq!("none".parse::<Modality>().unwrap(), Modality::NONE);
+        assert_eq!("ALL".parse::<Modality>().unwrap(), Modality::ALL);
+    }
+
+    #[test]
+    fn test_from_str_tolerates_whitespace_and_case() {
+        assert_eq!(
+            "  Audio  |  Text  ".parse::<Modality>().unwrap(),
+            Modality::AUDIO | Modality::TEXT
+        );
+    }
+
+    #[test]
+    fn test_from_str_invalid_token() {
+        let err = "audio | nonsense".parse::<Modality>().unwrap_err();
+        assert!(err.contains("nonsense"));
+        assert!(err.contains("position 1"));
+    }
+
+    #[test]
+    fn test_from_str_empty_expression() {
+        assert!("".parse::<Modality>().is_err());
+        assert!("   ".parse::<Modality>().is_err());
+    }
+
+    #[test]
+    fn test_register_participates_in_to_names_and_from_names() {
+        let pointcloud = Modality::register("lib_test_pointcloud").unwrap();
+        assert!(pointcloud.bits() > Modality::OTHER.bits());
+
+        let combo = Modality::AUDIO | pointcloud;
+        assert!(combo.to_names().contains(&"lib_test_pointcloud"));
+
+        let roundtrip = Modality::from_names(&["audio", "lib_test_pointcloud"]).unwrap();
+        assert_eq!(roundtrip, combo);
+    }
+
+    #[test]
+    fn test_register_rejects_duplicate_and_builtin_names() {
+        Modality::register("lib_test_depth").unwrap();
+        assert!(Modality::register("lib_test_depth").is_err());
+        assert!(Modality::register("audio").is_err());
+    }
+
+    #[test]
+    fn test_register_participates_in_iteration() {
+        let imu = Modality::register("lib_test_imu").unwrap();
+        let combo = Modality::TEXT | imu;
+        let collected: Vec<Modality> = combo.iter_modalities().collect();
+        assert_eq!(collected, vec![Modality::TEXT, imu]);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_as_names() {
+        let combo = Modality::AUDIO | Modality::TEXT;
+        let json = serde_json::to_string(&combo).unwrap();
+        assert_eq!(json, r#"["audio","text"]"#);
+    }
+
+    #[test]
+    fn test_deserialize_from_names() {
+        let combo: Modality = serde_json::from_str(r#"["audio","text"]"#).unwrap();
+        assert_eq!(combo, Modality::AUDIO | Modality::TEXT);
+    }
+
+    #[test]
+    fn test_deserialize_from_bitmask() {
+        let combo: Modality = serde_json::from_str(&(Modality::AUDIO | Modality::VIDEO).bits().to_string()).unwrap();
+        assert_eq!(combo, Modality::AUDIO | Modality::VIDEO);
+    }
+
+    #[test]
+    fn test_deserialize_rejects_unknown_name() {
+        let err = serde_json::from_str::<Modality>(r#"["nonsense"]"#).unwrap_err();
+        assert!(err.to_string().contains("Invalid modality name"));
+    }
+
+    #[test]
+    fn test_deserialize_rejects_unknown_bits() {
+        let err = serde_json::from_str::<Modality>("1073741824").unwrap_err();
+        assert!(err.to_string().contains("valid Modality bitmask"));
+    }
+
+    #[test]
+    fn test_bitmask_round_trip_with_registered_custom_modality() {
+        let pointcloud = Modality::register("serde_test_pointcloud").unwrap();
+        let combo = Modality::AUDIO | pointcloud;
+
+        let json = serde_json::to_string(&combo.bits()).unwrap();
+        let roundtripped: Modality = serde_json::from_str(&json).unwrap();
+        assert_eq!(roundtripped, combo);
+    }
 }
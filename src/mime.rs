@@ -0,0 +1,161 @@
+//! File-extension and MIME-type inference for [`Modality`].
+//!
+//! This is the common first step when ingesting a directory of
+//! heterogeneous multimodal data: given a path or a MIME type string,
+//! guess which [`Modality`] it represents. The default tables cover the
+//! common audio/image/text/video formats; callers can extend them at
+//! runtime with [`register_extension`] and [`register_mime`] to teach
+//! this module about domain-specific formats without forking the crate.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+
+use crate::Modality;
+
+fn extension_table() -> &'static Mutex<HashMap<String, Modality>> {
+    static TABLE: OnceLock<Mutex<HashMap<String, Modality>>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        for ext in ["wav", "flac", "mp3"] {
+            table.insert(ext.to_string(), Modality::AUDIO);
+        }
+        for ext in ["png", "jpg", "webp"] {
+            table.insert(ext.to_string(), Modality::IMAGE);
+        }
+        for ext in ["txt", "md", "json"] {
+            table.insert(ext.to_string(), Modality::TEXT);
+        }
+        for ext in ["mp4", "mkv", "mov"] {
+            table.insert(ext.to_string(), Modality::VIDEO);
+        }
+        Mutex::new(table)
+    })
+}
+
+fn mime_table() -> &'static Mutex<HashMap<String, Modality>> {
+    static TABLE: OnceLock<Mutex<HashMap<String, Modality>>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut table = HashMap::new();
+        table.insert("audio".to_string(), Modality::AUDIO);
+        table.insert("image".to_string(), Modality::IMAGE);
+        table.insert("text".to_string(), Modality::TEXT);
+        table.insert("video".to_string(), Modality::VIDEO);
+        Mutex::new(table)
+    })
+}
+
+/// Infers a [`Modality`] from a file path's extension.
+///
+/// Matching is case-insensitive and ignores any leading dot. Returns
+/// `Modality::OTHER` when the extension is present but not recognized,
+/// and `Modality::NONE` when the path has no extension at all.
+pub fn from_path<P: AsRef<Path>>(path: P) -> Modality {
+    let Some(ext) = path.as_ref().extension().and_then(|e| e.to_str()) else {
+        return Modality::NONE;
+    };
+    extension_table()
+        .lock()
+        .unwrap()
+        .get(ext.to_lowercase().as_str())
+        .copied()
+        .unwrap_or(Modality::OTHER)
+}
+
+/// Infers a [`Modality`] from a MIME type string, e.g. `"video/mp4"`.
+///
+/// Looks up the full `type/subtype` first, then falls back to matching
+/// just the top-level type (`"video"` in the example above). Returns
+/// `Modality::OTHER` when the MIME type is present but not recognized,
+/// and `Modality::NONE` for an empty string.
+pub fn from_mime(mime: &str) -> Modality {
+    let mime = mime.trim().to_lowercase();
+    if mime.is_empty() {
+        return Modality::NONE;
+    }
+
+    let table = mime_table();
+    let table = table.lock().unwrap();
+    if let Some(modality) = table.get(mime.as_str()) {
+        return *modality;
+    }
+
+    let top_level = mime.split('/').next().unwrap_or(mime.as_str());
+    table.get(top_level).copied().unwrap_or(Modality::OTHER)
+}
+
+/// Registers an additional file extension -> [`Modality`] mapping,
+/// overriding any existing mapping for that extension.
+///
+/// `extension` is matched case-insensitively and without a leading dot
+/// (e.g. pass `"pcd"`, not `".pcd"`).
+pub fn register_extension(extension: &str, modality: Modality) {
+    extension_table()
+        .lock()
+        .unwrap()
+        .insert(extension.to_lowercase(), modality);
+}
+
+/// Registers an additional MIME type -> [`Modality`] mapping,
+/// overriding any existing mapping for that type.
+///
+/// `mime_type` may be a full `type/subtype` (e.g. `"application/pdf"`)
+/// or a bare top-level type (e.g. `"font"`) used as a fallback for any
+/// subtype under it.
+pub fn register_mime(mime_type: &str, modality: Modality) {
+    mime_table()
+        .lock()
+        .unwrap()
+        .insert(mime_type.trim().to_lowercase(), modality);
+}
+
+#[cfg(test)]
+mod mime_tests {
+    use super::*;
+
+    #[test]
+    fn test_from_path_known_extensions() {
+        assert_eq!(from_path("clip.wav"), Modality::AUDIO);
+        assert_eq!(from_path("photo.PNG"), Modality::IMAGE);
+        assert_eq!(from_path("notes.md"), Modality::TEXT);
+        assert_eq!(from_path("movie.mp4"), Modality::VIDEO);
+    }
+
+    #[test]
+    fn test_from_path_unrecognized_extension_is_other() {
+        assert_eq!(from_path("archive.zip"), Modality::OTHER);
+    }
+
+    #[test]
+    fn test_from_path_no_extension_is_none() {
+        assert_eq!(from_path("README"), Modality::NONE);
+    }
+
+    #[test]
+    fn test_from_mime_falls_back_to_top_level_type() {
+        assert_eq!(from_mime("video/mp4"), Modality::VIDEO);
+        assert_eq!(from_mime("audio/ogg"), Modality::AUDIO);
+    }
+
+    #[test]
+    fn test_from_mime_unrecognized_is_other() {
+        assert_eq!(from_mime("application/pdf"), Modality::OTHER);
+    }
+
+    #[test]
+    fn test_from_mime_empty_is_none() {
+        assert_eq!(from_mime(""), Modality::NONE);
+        assert_eq!(from_mime("   "), Modality::NONE);
+    }
+
+    #[test]
+    fn test_register_extension_and_mime() {
+        register_extension("pcd", Modality::OTHER);
+        assert_eq!(from_path("scan.pcd"), Modality::OTHER);
+
+        // Use a key no other test reads, since the extension/MIME tables
+        // are process-wide singletons shared across the test harness.
+        register_mime("application/x-modalities-test", Modality::TEXT);
+        assert_eq!(from_mime("application/x-modalities-test"), Modality::TEXT);
+    }
+}